@@ -1,9 +1,9 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
 use bit_field::BitField;
 use embedded_hal::{
     digital::v2::OutputPin,
-    blocking::delay::DelayMs,
+    blocking::delay::DelayUs,
 };
 
 /// A device driver for the AD9959 direct digital synthesis (DDS) chip.
@@ -41,6 +41,7 @@ pub enum Mode {
 
 /// The configuration registers within the AD9959 DDS device. The values of each register are
 /// equivalent to the address.
+#[derive(Copy, Clone)]
 pub enum Register {
     CSR = 0x00,
     FR1 = 0x01,
@@ -77,6 +78,99 @@ pub enum Channel {
     Four = 3,
 }
 
+/// A bitmask of output channels. Register writes and the subsequent IO_UPDATE latch apply to
+/// every channel selected in the mask at once, so channels sharing a mask are guaranteed to
+/// update on the same IO_UPDATE edge.
+#[derive(Copy, Clone)]
+pub struct Channels(u8);
+
+impl Channels {
+    /// No channels selected.
+    pub const NONE: Channels = Channels(0);
+
+    /// All four output channels selected.
+    pub const ALL: Channels = Channels(0xf);
+
+    /// Add `channel` to the mask.
+    pub fn with(mut self, channel: Channel) -> Self {
+        self.0.set_bit(channel as usize, true);
+        self
+    }
+}
+
+impl From<Channel> for Channels {
+    fn from(channel: Channel) -> Self {
+        Channels::NONE.with(channel)
+    }
+}
+
+/// Selects which tuning parameter a channel's linear sweep (ramp) generator slews between its
+/// two endpoints.
+#[derive(Copy, Clone)]
+pub enum SweepParameter {
+    Frequency,
+    Phase,
+    Amplitude,
+}
+
+impl SweepParameter {
+    /// The AFP Select bits (CFR bits 1:0) that pick this parameter as the one under sweep
+    /// control.
+    fn afp_select(self) -> u8 {
+        match self {
+            SweepParameter::Amplitude => 0b01,
+            SweepParameter::Phase => 0b10,
+            SweepParameter::Frequency => 0b11,
+        }
+    }
+
+    /// The register holding the sweep's starting (lower) endpoint. The ending (upper) endpoint
+    /// always lives in CW1, regardless of parameter.
+    fn start_register(self) -> Register {
+        match self {
+            SweepParameter::Frequency => Register::CFTW0,
+            SweepParameter::Phase => Register::CPOW0,
+            SweepParameter::Amplitude => Register::ACR,
+        }
+    }
+
+    /// Encode a raw tuning `value` at the width this parameter occupies in CW1 and the RDW/FDW
+    /// delta registers: the full 32 bits for a frequency sweep, or the low 16 bits for a phase or
+    /// amplitude sweep, matching the word width `start_register` stores the starting endpoint in
+    /// (CPOW0/ACR do not carry a 32-bit value either).
+    ///
+    /// Returns the big-endian encoded bytes and the number of leading bytes that are significant.
+    fn encode(self, value: u32) -> ([u8; 4], usize) {
+        match self {
+            SweepParameter::Frequency => (value.to_be_bytes(), 4),
+            SweepParameter::Phase | SweepParameter::Amplitude => {
+                let word = (value as u16).to_be_bytes();
+                ([word[0], word[1], 0, 0], 2)
+            },
+        }
+    }
+}
+
+/// The endpoints and step rates for a channel's linear sweep (ramp) generator. See
+/// [`Ad9959::configure_sweep`].
+#[derive(Copy, Clone)]
+pub struct SweepConfig {
+    /// Which tuning parameter ramps between the endpoints.
+    pub parameter: SweepParameter,
+    /// The sweep's starting (lower) endpoint, as a raw tuning word for `parameter`.
+    pub start: u32,
+    /// The sweep's ending (upper) endpoint, as a raw tuning word for `parameter`.
+    pub stop: u32,
+    /// The per-step increment applied while ramping from `start` toward `stop`.
+    pub rising_delta_word: u32,
+    /// The per-step decrement applied while ramping from `stop` back toward `start`.
+    pub falling_delta_word: u32,
+    /// The number of SYNC_CLK periods between rising steps. Must fit in a 4-bit nibble (0..=15).
+    pub rising_step_rate: u8,
+    /// The number of SYNC_CLK periods between falling steps. Must fit in a 4-bit nibble (0..=15).
+    pub falling_step_rate: u8,
+}
+
 /// Possible errors generated by the AD9959 driver.
 #[derive(Debug)]
 pub enum Error<InterfaceE> {
@@ -93,82 +187,53 @@ impl <InterfaceE> From<InterfaceE> for Error<InterfaceE> {
     }
 }
 
-impl <PinE, InterfaceE, INTERFACE, DELAY, UPDATE> Ad9959<INTERFACE, DELAY, UPDATE>
+/// The number of bytes in a serialized channel profile, as produced by
+/// [`Ad9959::serialize_profile`]: one CSR channel-select byte, four CFTW0 bytes, two CPOW0 bytes,
+/// and three ACR bytes.
+pub const PROFILE_SIZE: usize = 10;
+
+impl <InterfaceE, INTERFACE, DELAY, UPDATE> Ad9959<INTERFACE, DELAY, UPDATE>
 where
     INTERFACE: Interface<Error = InterfaceE>,
-    DELAY: DelayMs<u8>,
-    UPDATE: OutputPin<Error = PinE>,
-
 {
-    pub fn new<RST>(interface: INTERFACE,
-                    reset_pin: &mut RST,
-                    io_update: UPDATE,
-                    delay: DELAY,
-                    clock_frequency: u32) -> Result<Self, Error<InterfaceE>>
-    where
-        RST: OutputPin,
-    {
-        let mut ad9959 = Ad9959 {
-            interface: interface,
-            io_update: io_update,
-            delay: delay,
-            reference_clock_frequency: clock_frequency,
-            system_clock_multiplier: 1,
-        };
-
-       ad9959.io_update.set_low().or_else(|_| Err(Error::Pin))?;
-
-        // Reset the AD9959
-        reset_pin.set_high().or_else(|_| Err(Error::Pin))?;
-
-        // Delay for a clock cycle to allow the device to reset.
-        ad9959.delay.delay_ms((1000.0 / clock_frequency as f32) as u8);
-
-        reset_pin.set_low().or_else(|_| Err(Error::Pin))?;
-
-        // multiple gotchas:
-        // 1. only four bit is compatible for reads
-        //    a) qspi listens (single-bit) on io1 vs dds sends on io0 (two-wire) or io2 (three-wire)
-        //    b) two-bit is incompatible because io3=hold=sync_i/o is driven high (might be possible
-        //       with io3 not af10 but low gpio)
-        // 2. even entering 4 bit mode from 1 bit (reset) requires forcing sync_i/o=io3 low
-        //
-        // the only simple solution is to use 4-bit mode exlusively and the only way to enter it is
-        // to construct the proper padded 4-bit sequence while the dds is still in 1 bit mode
-        //
-        // data to be sent is is 0x00 0xf6 (write CSR, default all DDS on, MSB first, but four wire)
-        // with 4-bit it's then 0x00 0x00 0x00 0x00 0x11 0x11 0x01 0x10
-        // and the first byte is taken up as the instruction
-        
-        // Configure the interface to the desired mode.
-       ad9959.interface.configure_mode(Mode::FourBitSerial)?;
-
-       // Program the interface configuration in the AD9959.
-       let csr: [u8; 7] = [0x00, 0x00, 0x00, 0x11, 0x11, 0x01, 0x10];
-       ad9959.interface.write(0, &csr)?;
+    /// Validate a reference clock / PLL multiplier combination against the AD9959's clocking
+    /// constraints and select the VCO gain band for it.
+    ///
+    /// The REFCLK multiplier must either be 1 (PLL bypassed) or in the range 4..=20. With the
+    /// multiplier bypassed, the reference clock must be at least 1 MHz; with the PLL enabled, it
+    /// must be at least 10 MHz. The resulting system clock must not exceed 500 MHz, and, whenever
+    /// the PLL is enabled, must fall within one of the two VCO gain bands (100-160 MHz for low
+    /// gain, 255-500 MHz for high gain) -- frequencies in the dead zone between the bands cannot
+    /// be synthesized with the PLL enabled.
+    ///
+    /// Returns:
+    /// The actual system clock frequency and whether the high VCO gain bit should be set.
+    fn validate_clocking(reference_clock_frequency: u32, multiplier: u8) -> Result<(f32, bool), Error<InterfaceE>> {
+        if multiplier != 1 && !(4..=20).contains(&multiplier) {
+            return Err(Error::Bounds);
+        }
 
-       // Latch the configuration registers to make them active.
-       ad9959.latch_configuration()?;
+        let minimum_reference_clock = if multiplier == 1 { 1_000_000 } else { 10_000_000 };
+        if reference_clock_frequency < minimum_reference_clock {
+            return Err(Error::Frequency);
+        }
 
-       let mut csr: [u8; 1] = [0];
-        ad9959.interface.read(Register::CSR as u8, &mut csr)?;
-        if csr[0] != 0xf6 {
-            return Err(Error::Identification)
+        let system_clock_frequency = reference_clock_frequency as f32 * multiplier as f32;
+        if system_clock_frequency > 500_000_000.0 {
+            return Err(Error::Frequency);
         }
 
-       // Set the clock frequency to configure the device as necessary.
-       ad9959.set_clock_frequency(clock_frequency)?;
-        Ok(ad9959)
-    }
+        if multiplier == 1 {
+            return Ok((system_clock_frequency, false));
+        }
 
-    fn latch_configuration(&mut self) -> Result<(), Error<InterfaceE>> {
-       self.io_update.set_high().or_else(|_| Err(Error::Pin))?;
-       // The SYNC_CLK is 1/4 the system clock frequency. The IO_UPDATE pin must be latched for one
-       // full SYNC_CLK pulse to register. For safety, we latch for 5 here.
-       self.delay.delay_ms((5000.0 / self.system_clock_frequency()) as u8);
-       self.io_update.set_low().or_else(|_| Err(Error::Pin))?;
+        let vco_high_gain = match system_clock_frequency {
+            f if (255_000_000.0..=500_000_000.0).contains(&f) => true,
+            f if (100_000_000.0..=160_000_000.0).contains(&f) => false,
+            _ => return Err(Error::Frequency),
+        };
 
-       Ok(())
+        Ok((system_clock_frequency, vco_high_gain))
     }
 
     /// Specify the reference clock frequency for the chip.
@@ -176,14 +241,15 @@ where
     /// Arguments:
     /// * `clock_frequency` - The refrence clock frequency provided to the AD9959 core.
     pub fn set_clock_frequency(&mut self, clock_frequency: u32) -> Result<(), Error<InterfaceE>> {
-        // TODO: Check validity of the clock frequency.
+        let (_, vco_high_gain) = Self::validate_clocking(clock_frequency, self.system_clock_multiplier)?;
 
-        // TODO: If the input clock is above 255 MHz, enable the VCO gain control bit.
+        let mut fr1: [u8; 3] = [0, 0, 0];
+        self.interface.read(Register::FR1 as u8, &mut fr1)?;
+        fr1[0].set_bit(7, vco_high_gain);
+        self.interface.write(Register::FR1 as u8, &fr1)?;
 
         self.reference_clock_frequency = clock_frequency;
 
-        // TODO: Update the system clock frequency given the current PLL configurtation.
-
         Ok(())
     }
 
@@ -194,37 +260,35 @@ where
     ///
     /// Returns:
     /// The actual frequency configured for the internal system clock.
+    ///
+    /// Note:
+    /// Every multiplier (PLL bypass, or 4..=20) that `validate_clocking` accepts for the current
+    /// reference clock is tried; the one whose resulting system clock is closest to `frequency`
+    /// is selected, rather than picking the first in-range candidate by truncation.
     pub fn configure_system_clock(&mut self, frequency: f32) -> Result<f32, Error<InterfaceE>> {
-        if frequency > 500_000_000.0 {
-            return Err(Error::Frequency);
-        }
-
-        let prescaler: u8 = match (frequency / self.reference_clock_frequency as f32) as u32 {
-            0 => return Err(Error::Frequency),
-
-            // We cannot achieve this frequency with the PLL. Assume the PLL is not used.
-            1 | 2 | 3 => 1,
-            _ => {
-                // Configure the PLL prescaler.
-                let mut prescaler = (frequency / self.reference_clock_frequency as f32) as u8;
-                if prescaler > 20 {
-                    prescaler = 20;
-                }
-
-                prescaler
-            },
-        };
+        let multiplier = [1, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20]
+            .iter()
+            .filter_map(|&candidate| {
+                Self::validate_clocking(self.reference_clock_frequency, candidate)
+                    .ok()
+                    .map(|(system_clock_frequency, _)| (candidate, system_clock_frequency))
+            })
+            .min_by(|(_, a), (_, b)| (a - frequency).abs().partial_cmp(&(b - frequency).abs()).unwrap())
+            .map(|(candidate, _)| candidate)
+            .ok_or(Error::Frequency)?;
+
+        let (system_clock_frequency, vco_high_gain) =
+            Self::validate_clocking(self.reference_clock_frequency, multiplier)?;
 
         // TODO: Update / disable any enabled channels?
         let mut fr1: [u8; 3] = [0, 0, 0];
         self.interface.read(Register::FR1 as u8, &mut fr1)?;
-        fr1[0].set_bits(2..=6, prescaler);
-        let vco_range = frequency > 255e6;
-        fr1[0].set_bit(7, vco_range);
+        fr1[0].set_bits(2..=6, multiplier);
+        fr1[0].set_bit(7, vco_high_gain);
         self.interface.write(Register::FR1 as u8, &fr1)?;
-        self.system_clock_multiplier = prescaler;
+        self.system_clock_multiplier = multiplier;
 
-        Ok(self.system_clock_frequency())
+        Ok(system_clock_frequency)
     }
 
     /// Perform a self-test of the communication interface.
@@ -292,84 +356,394 @@ where
         Ok(())
     }
 
-    fn modify_channel(&mut self, channel: Channel, register: Register, data: &[u8]) -> Result<(), Error<InterfaceE>> {
+    fn phase_tuning_word(phase_degrees: f32) -> Result<u16, Error<InterfaceE>> {
+        if phase_degrees > 360.0 || phase_degrees < 0.0 {
+            return Err(Error::Bounds);
+        }
+
+        Ok((phase_degrees / 360.0 * 2_u32.pow(14) as f32) as u16)
+    }
+
+    fn amplitude_tuning_word(amplitude: f32) -> Result<u16, Error<InterfaceE>> {
+        if amplitude < 0.0 || amplitude > 1.0 {
+            return Err(Error::Bounds);
+        }
+
+        Ok((amplitude / 1.0 * 2_u16.pow(10) as f32) as u16)
+    }
+
+    /// Encode an amplitude control word into the 3-byte ACR payload, enabling the amplitude
+    /// multiplier whenever the channel is not running at full scale.
+    fn acr_bytes(amplitude_control: u16) -> [u8; 3] {
+        let mut acr: [u8; 3] = [0, amplitude_control.to_be_bytes()[0], amplitude_control.to_be_bytes()[1]];
+        acr[1].set_bit(4, amplitude_control < 0x3ff);
+        acr
+    }
+
+    fn frequency_tuning_word(&self, frequency: f32) -> Result<u32, Error<InterfaceE>> {
+        if frequency < 0.0 || frequency > self.system_clock_frequency() {
+            return Err(Error::Bounds);
+        }
+
+        Ok(((frequency / self.system_clock_frequency()) * u32::max_value() as f32) as u32)
+    }
+
+    /// Serialize a complete channel profile (frequency, phase, and amplitude) into its raw
+    /// register byte stream without touching the bus or the IO_UPDATE pin.
+    ///
+    /// This is intended for callers that push profiles out over a DMA-driven transfer and latch
+    /// IO_UPDATE from a hardware timer, instead of going through [`Ad9959::set_frequency`],
+    /// [`Ad9959::set_phase`], and [`Ad9959::set_amplitude`], each of which blocks on a
+    /// read-modify-write-latch cycle.
+    ///
+    /// Arguments:
+    /// * `channel` - The channel the profile applies to.
+    /// * `frequency` - The desired output frequency in Hz.
+    /// * `phase_degrees` - The desired phase offset within [0, 360] degrees.
+    /// * `amplitude` - A normalized amplitude setting [0, 1].
+    /// * `profile` - The buffer the serialized profile is written into.
+    ///
+    /// Returns:
+    /// The channel-select byte followed by the CFTW0, CPOW0, and ACR payloads, in the order they
+    /// must be written to the device.
+    pub fn serialize_profile<'a>(
+        &self,
+        channel: Channel,
+        frequency: f32,
+        phase_degrees: f32,
+        amplitude: f32,
+        profile: &'a mut [u8; PROFILE_SIZE],
+    ) -> Result<&'a [u8], Error<InterfaceE>> {
+        let tuning_word = self.frequency_tuning_word(frequency)?;
+        let phase_offset = Self::phase_tuning_word(phase_degrees)?;
+        let amplitude_control = Self::amplitude_tuning_word(amplitude)?;
+
+        profile[0] = 0;
+        profile[0].set_bit(4 + channel as usize, true);
+        profile[1..5].copy_from_slice(&tuning_word.to_be_bytes());
+        profile[5..7].copy_from_slice(&phase_offset.to_be_bytes());
+        profile[7..10].copy_from_slice(&Self::acr_bytes(amplitude_control));
+
+        Ok(&profile[..])
+    }
+}
+
+impl <PinE, InterfaceE, INTERFACE, DELAY, UPDATE> Ad9959<INTERFACE, DELAY, UPDATE>
+where
+    INTERFACE: Interface<Error = InterfaceE>,
+    DELAY: DelayUs<u16>,
+    UPDATE: OutputPin<Error = PinE>,
+
+{
+    pub fn new<RST>(interface: INTERFACE,
+                    reset_pin: &mut RST,
+                    io_update: UPDATE,
+                    delay: DELAY,
+                    clock_frequency: u32) -> Result<Self, Error<InterfaceE>>
+    where
+        RST: OutputPin,
+    {
+        let mut ad9959 = Ad9959 {
+            interface: interface,
+            io_update: io_update,
+            delay: delay,
+            reference_clock_frequency: clock_frequency,
+            system_clock_multiplier: 1,
+        };
+
+       ad9959.io_update.set_low().or_else(|_| Err(Error::Pin))?;
+
+        // Reset the AD9959
+        reset_pin.set_high().or_else(|_| Err(Error::Pin))?;
+
+        // Delay for a clock cycle to allow the device to reset.
+        let reset_delay_us = ((1_000_000 + clock_frequency - 1) / clock_frequency).max(1) as u16;
+        ad9959.delay.delay_us(reset_delay_us);
+
+        reset_pin.set_low().or_else(|_| Err(Error::Pin))?;
+
+        // multiple gotchas:
+        // 1. only four bit is compatible for reads
+        //    a) qspi listens (single-bit) on io1 vs dds sends on io0 (two-wire) or io2 (three-wire)
+        //    b) two-bit is incompatible because io3=hold=sync_i/o is driven high (might be possible
+        //       with io3 not af10 but low gpio)
+        // 2. even entering 4 bit mode from 1 bit (reset) requires forcing sync_i/o=io3 low
+        //
+        // the only simple solution is to use 4-bit mode exlusively and the only way to enter it is
+        // to construct the proper padded 4-bit sequence while the dds is still in 1 bit mode
+        //
+        // data to be sent is is 0x00 0xf6 (write CSR, default all DDS on, MSB first, but four wire)
+        // with 4-bit it's then 0x00 0x00 0x00 0x00 0x11 0x11 0x01 0x10
+        // and the first byte is taken up as the instruction
+        
+        // Configure the interface to the desired mode.
+       ad9959.interface.configure_mode(Mode::FourBitSerial)?;
+
+       // Program the interface configuration in the AD9959.
+       let csr: [u8; 7] = [0x00, 0x00, 0x00, 0x11, 0x11, 0x01, 0x10];
+       ad9959.interface.write(0, &csr)?;
+
+       // Latch the configuration registers to make them active.
+       ad9959.latch_configuration()?;
+
+       let mut csr: [u8; 1] = [0];
+        ad9959.interface.read(Register::CSR as u8, &mut csr)?;
+        if csr[0] != 0xf6 {
+            return Err(Error::Identification)
+        }
+
+       // Set the clock frequency to configure the device as necessary.
+       ad9959.set_clock_frequency(clock_frequency)?;
+        Ok(ad9959)
+    }
+
+    fn latch_configuration(&mut self) -> Result<(), Error<InterfaceE>> {
+       self.io_update.set_high().or_else(|_| Err(Error::Pin))?;
+       // The SYNC_CLK is 1/4 the system clock frequency. The IO_UPDATE pin must be latched for one
+       // full SYNC_CLK pulse to register. For safety, we latch for 5 here, rounded up to a whole
+       // microsecond (via integer ceiling division, since this is a `#![no_std]` crate without
+       // `libm`) so the hold time is never shorter than required.
+       let system_clock_frequency = self.system_clock_multiplier as u32 * self.reference_clock_frequency;
+       let latch_delay_us = ((20_000_000 + system_clock_frequency - 1) / system_clock_frequency).max(1) as u16;
+       self.delay.delay_us(latch_delay_us);
+       self.io_update.set_low().or_else(|_| Err(Error::Pin))?;
+
+       Ok(())
+    }
+
+    /// Select `channels`, apply `writes` to their registers, and latch them all on the same
+    /// IO_UPDATE edge before restoring the previous channel selection.
+    fn modify_channel(&mut self, channels: impl Into<Channels>, writes: &[(Register, &[u8])]) -> Result<(), Error<InterfaceE>> {
+        let channels = channels.into();
+
         let mut csr: [u8; 1] = [0];
         self.interface.read(Register::CSR as u8, &mut csr)?;
 
         let mut new_csr = csr;
-        new_csr[0].set_bits(4..8, 0);
-        new_csr[0].set_bit(4 + channel as usize, true);
+        new_csr[0].set_bits(4..8, channels.0);
 
         self.interface.write(Register::CSR as u8, &new_csr)?;
 
-        self.interface.write(register as u8, &data)?;
+        for (register, data) in writes {
+            self.interface.write(*register as u8, data)?;
+        }
 
-        // Latch the configuration and restore the previous CSR. Note that the re-enable of the
-        // channel happens immediately, so the CSR update does not need to be latched.
+        // Latch the configuration and restore the previous CSR. Note that all enabled channels
+        // share this single register write, so they update on the same IO_UPDATE edge. The
+        // re-enable of the previous channel selection happens immediately, so the CSR restore
+        // does not need to be latched.
         self.latch_configuration()?;
         self.interface.write(Register::CSR as u8, &csr)?;
 
         Ok(())
     }
     
-    /// Configure the phase of a specified channel.
+    /// Configure the phase of a specified channel, or several channels at once.
     ///
     /// Arguments:
-    /// * `channel` - The channel to configure the frequency of.
+    /// * `channels` - The channel, or [`Channels`] mask of channels, to configure the phase of.
+    ///   Channels sharing a mask are latched on the same IO_UPDATE edge.
     /// * `phase_degrees` - The desired phase offset within [0, 360] degrees.
     ///
     /// Returns:
-    /// The actual programmed phase offset of the channel in degrees.
-    pub fn set_phase(&mut self, channel: Channel, phase_degrees: f32) -> Result<f32, Error<InterfaceE>> {
-        if phase_degrees > 360.0 || phase_degrees < 0.0 {
-            return Err(Error::Bounds);
-        }
-
-        let phase_offset: u16 = (phase_degrees / 360.0 * 2_u32.pow(14) as f32) as u16;
-        self.modify_channel(channel, Register::CPOW0, &phase_offset.to_be_bytes())?;
+    /// The actual programmed phase offset in degrees.
+    pub fn set_phase(&mut self, channels: impl Into<Channels>, phase_degrees: f32) -> Result<f32, Error<InterfaceE>> {
+        let phase_offset = Self::phase_tuning_word(phase_degrees)?;
+        self.modify_channel(channels, &[(Register::CPOW0, &phase_offset.to_be_bytes()[..])])?;
         Ok((phase_offset as f32 / 2_u32.pow(14) as f32) * 360.0)
     }
 
-    /// Configure the amplitude of a specified channel.
+    /// Configure the amplitude of a specified channel, or several channels at once.
     ///
     /// Arguments:
-    /// * `channel` - The channel to configure the frequency of.
+    /// * `channels` - The channel, or [`Channels`] mask of channels, to configure the amplitude
+    ///   of. Channels sharing a mask are latched on the same IO_UPDATE edge.
     /// * `amplitude` - A normalized amplitude setting [0, 1].
     ///
     /// Returns:
-    /// The actual normalized amplitude of the channel relative to full-scale range.
-    pub fn set_amplitude(&mut self, channel: Channel, amplitude: f32) -> Result<f32, Error<InterfaceE>> {
-        if amplitude < 0.0 || amplitude > 1.0 {
-            return Err(Error::Bounds);
-        }
-
-        let amplitude_control: u16 = (amplitude / 1.0 * 2_u16.pow(10) as f32) as u16;
-        let mut acr: [u8; 3] = [0, amplitude_control.to_be_bytes()[0], amplitude_control.to_be_bytes()[1]];
-
-        // Enable the amplitude multiplier for the channel if required.
-        acr[1].set_bit(4, amplitude_control < 0x3ff);
-
-        self.modify_channel(channel, Register::ACR, &acr)?;
+    /// The actual normalized amplitude relative to full-scale range.
+    pub fn set_amplitude(&mut self, channels: impl Into<Channels>, amplitude: f32) -> Result<f32, Error<InterfaceE>> {
+        let amplitude_control = Self::amplitude_tuning_word(amplitude)?;
+        self.modify_channel(channels, &[(Register::ACR, &Self::acr_bytes(amplitude_control)[..])])?;
 
         Ok(amplitude_control as f32 / 2_u16.pow(10) as f32)
     }
 
-    /// Configure the frequency of a specified channel.
+    /// Configure the frequency of a specified channel, or several channels at once.
     ///
     /// Arguments:
-    /// * `channel` - The channel to configure the frequency of.
+    /// * `channels` - The channel, or [`Channels`] mask of channels, to configure the frequency
+    ///   of. Channels sharing a mask are latched on the same IO_UPDATE edge, which keeps them
+    ///   phase-coherent.
     /// * `frequency` - The desired output frequency in Hz.
     ///
     /// Returns:
-    /// The actual programmed frequency of the channel.
-    pub fn set_frequency(&mut self, channel: Channel, frequency: f32) -> Result<f32, Error<InterfaceE>> {
-        if frequency < 0.0 || frequency > self.system_clock_frequency() {
+    /// The actual programmed frequency.
+    pub fn set_frequency(&mut self, channels: impl Into<Channels>, frequency: f32) -> Result<f32, Error<InterfaceE>> {
+        let tuning_word = self.frequency_tuning_word(frequency)?;
+        self.modify_channel(channels, &[(Register::CFTW0, &tuning_word.to_be_bytes()[..])])?;
+        Ok((tuning_word as f32 / u32::max_value() as f32) * self.system_clock_frequency())
+    }
+
+    /// Configure a channel's linear sweep (ramp) generator so it slews `config.parameter` between
+    /// two endpoints instead of jumping, using the chip's LSRR/RDW/FDW ramp hardware.
+    ///
+    /// Arguments:
+    /// * `channel` - The channel to configure the sweep for.
+    /// * `config` - The sweep endpoints and step rates. See [`SweepConfig`].
+    ///
+    /// Note:
+    /// The register writes are committed with the same IO_UPDATE latch that
+    /// [`Ad9959::trigger_sweep`] issues, so the latch at the end of this call also triggers the
+    /// sweep. Call [`Ad9959::trigger_sweep`] afterwards to trigger (or reverse) the ramp again.
+    pub fn configure_sweep(&mut self, channel: Channel, config: SweepConfig) -> Result<(), Error<InterfaceE>> {
+        if config.rising_step_rate > 0xf || config.falling_step_rate > 0xf {
             return Err(Error::Bounds);
         }
 
-        let tuning_word: u32 = ((frequency as f32 / self.system_clock_frequency()) * u32::max_value()
-            as f32) as u32;
-        self.modify_channel(channel, Register::CFTW0, &tuning_word.to_be_bytes())?;
-        Ok((tuning_word as f32 / u32::max_value() as f32) * self.system_clock_frequency())
+        let mut cfr: [u8; 3] = [0, 0, 0];
+        self.interface.read(Register::CFR as u8, &mut cfr)?;
+        cfr[0].set_bit(7, true);
+        cfr[0].set_bits(0..2, config.parameter.afp_select());
+
+        // LSRR holds the falling step rate in its first byte and the rising step rate in its
+        // second byte, each counting SYNC_CLK periods per step (limited to 0..=15 above).
+        let lsrr: [u8; 2] = [config.falling_step_rate, config.rising_step_rate];
+
+        let (rdw_bytes, rdw_len) = config.parameter.encode(config.rising_delta_word);
+        let (fdw_bytes, fdw_len) = config.parameter.encode(config.falling_delta_word);
+        let (stop_bytes, stop_len) = config.parameter.encode(config.stop);
+
+        let (start_bytes, start_len): ([u8; 4], usize) = match config.parameter {
+            SweepParameter::Frequency => (config.start.to_be_bytes(), 4),
+            SweepParameter::Phase => {
+                let word = (config.start as u16).to_be_bytes();
+                ([word[0], word[1], 0, 0], 2)
+            },
+            SweepParameter::Amplitude => {
+                let acr = Self::acr_bytes(config.start as u16);
+                ([acr[0], acr[1], acr[2], 0], 3)
+            },
+        };
+
+        self.modify_channel(
+            channel,
+            &[
+                (Register::CFR, &cfr[..]),
+                (Register::LSRR, &lsrr[..]),
+                (Register::RDW, &rdw_bytes[..rdw_len]),
+                (Register::FDW, &fdw_bytes[..fdw_len]),
+                (config.parameter.start_register(), &start_bytes[..start_len]),
+                (Register::CW1, &stop_bytes[..stop_len]),
+            ],
+        )
+    }
+
+    /// Trigger a previously configured sweep via the same IO_UPDATE latch path used to commit
+    /// other channel register writes. If the sweep is held at an endpoint, this releases it to
+    /// ramp toward the opposite one.
+    pub fn trigger_sweep(&mut self) -> Result<(), Error<InterfaceE>> {
+        self.latch_configuration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockInterface;
+
+    impl Interface for MockInterface {
+        type Error = ();
+
+        fn configure_mode(&mut self, _mode: Mode) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn write(&mut self, _addr: u8, _data: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn read(&mut self, _addr: u8, _dest: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    // `serialize_profile` only reads the reference clock / multiplier fields, so the delay and
+    // IO_UPDATE pin types never need to satisfy `DelayUs`/`OutputPin` for this test instance.
+    fn mock_ad9959(reference_clock_frequency: u32, system_clock_multiplier: u8) -> Ad9959<MockInterface, (), ()> {
+        Ad9959 {
+            interface: MockInterface,
+            delay: (),
+            reference_clock_frequency,
+            system_clock_multiplier,
+            io_update: (),
+        }
+    }
+
+    #[test]
+    fn validate_clocking_rejects_out_of_range_multiplier() {
+        let result = Ad9959::<MockInterface, (), ()>::validate_clocking(10_000_000, 2);
+        assert!(matches!(result, Err(Error::Bounds)));
+    }
+
+    #[test]
+    fn validate_clocking_allows_pll_bypass() {
+        let result = Ad9959::<MockInterface, (), ()>::validate_clocking(10_000_000, 1);
+        assert_eq!(result.unwrap(), (10_000_000.0, false));
+    }
+
+    #[test]
+    fn validate_clocking_rejects_the_dead_zone_between_gain_bands() {
+        let result = Ad9959::<MockInterface, (), ()>::validate_clocking(20_000_000, 10);
+        assert!(matches!(result, Err(Error::Frequency)));
+    }
+
+    #[test]
+    fn validate_clocking_selects_high_vco_gain_above_the_dead_zone() {
+        let (system_clock_frequency, vco_high_gain) = Ad9959::<MockInterface, (), ()>::validate_clocking(20_000_000, 20).unwrap();
+        assert_eq!(system_clock_frequency, 400_000_000.0);
+        assert!(vco_high_gain);
+    }
+
+    #[test]
+    fn channels_mask_accumulates_requested_channels() {
+        let channels = Channels::NONE.with(Channel::One).with(Channel::Three);
+        assert_eq!(channels.0, 0b0101);
+    }
+
+    #[test]
+    fn channels_all_covers_every_channel() {
+        assert_eq!(Channels::ALL.0, 0b1111);
+    }
+
+    #[test]
+    fn sweep_parameter_encode_matches_its_register_width() {
+        let (bytes, len) = SweepParameter::Frequency.encode(0x1234_5678);
+        assert_eq!(&bytes[..len], &[0x12, 0x34, 0x56, 0x78]);
+
+        let (bytes, len) = SweepParameter::Phase.encode(0x1234_5678);
+        assert_eq!(&bytes[..len], &[0x56, 0x78]);
+    }
+
+    #[test]
+    fn sweep_parameter_afp_select_is_distinct_per_parameter() {
+        assert_ne!(SweepParameter::Frequency.afp_select(), SweepParameter::Phase.afp_select());
+        assert_ne!(SweepParameter::Phase.afp_select(), SweepParameter::Amplitude.afp_select());
+        assert_ne!(SweepParameter::Frequency.afp_select(), SweepParameter::Amplitude.afp_select());
+    }
+
+    #[test]
+    fn serialize_profile_lays_out_csr_cftw0_cpow0_and_acr_in_order() {
+        let ad9959 = mock_ad9959(25_000_000, 20);
+        let mut profile = [0u8; PROFILE_SIZE];
+
+        let bytes = ad9959.serialize_profile(Channel::Two, 0.0, 0.0, 0.0, &mut profile).unwrap();
+
+        assert_eq!(bytes.len(), PROFILE_SIZE);
+        assert_eq!(bytes[0], 1 << (4 + Channel::Two as usize));
+        assert_eq!(bytes[1..5], [0, 0, 0, 0]);
+        assert_eq!(bytes[5..7], [0, 0]);
+        assert_eq!(bytes[7..10], [0, 0x10, 0]);
     }
 }